@@ -36,15 +36,19 @@ enum DataType {
     Int32,
     Int48,
     Int64,
-    Unknown,
+    Float64,
+    Zero,
+    One,
+    Blob,
 }
 
 #[derive(Debug)]
 enum DataValue {
     Null,
     String(String),
-    Int(i8),
-    Unknown,
+    Int(i64),
+    Float(f64),
+    Blob(Vec<u8>),
 }
 
 fn get_type_definition(type_code: u64) -> (DataType, usize) {
@@ -56,11 +60,14 @@ fn get_type_definition(type_code: u64) -> (DataType, usize) {
         4 => (DataType::Int32, 4),
         5 => (DataType::Int48, 6),
         6 => (DataType::Int64, 8),
+        7 => (DataType::Float64, 8),
+        8 => (DataType::Zero, 0),
+        9 => (DataType::One, 0),
         _ => {
             if type_code % 2 == 0 {
                 let size = ((type_code - 12) / 2) as usize;
 
-                (DataType::Unknown, size)
+                (DataType::Blob, size)
             } else {
                 let size = ((type_code - 13) / 2) as usize;
 
@@ -70,15 +77,90 @@ fn get_type_definition(type_code: u64) -> (DataType, usize) {
     }
 }
 
+// Sign-extends a 1-8 byte big-endian integer (serial types 1-6) into an i64.
+fn decode_be_int(bytes: &[u8]) -> i64 {
+    let mut value = i64::from(bytes[0] as i8);
+    for &byte in &bytes[1..] {
+        value = (value << 8) | i64::from(byte);
+    }
+
+    value
+}
+
+// Parses a serialized record (header + column values), starting at its
+// header-size varint. Used for both table rows and index entries, which
+// share the same on-disk record format.
+fn parse_record(bytes: &[u8]) -> Result<Vec<DataValue>> {
+    let (header_size, offset) = decode_varint(bytes)?;
+    let header_end_offset = header_size as usize;
+    let mut header_offset = offset;
+
+    let mut type_definitions: Vec<(DataType, usize)> = vec![];
+    while header_offset < header_end_offset {
+        let (content, offset) = decode_varint(&bytes[header_offset..header_end_offset])?;
+        header_offset += offset;
+
+        type_definitions.push(get_type_definition(content));
+    }
+
+    let mut values: Vec<DataValue> = vec![];
+    let mut values_offset = header_end_offset;
+    for type_definition in type_definitions {
+        let value_length = type_definition.1;
+        let value_bytes = &bytes[values_offset..values_offset + value_length];
+
+        let value = match type_definition.0 {
+            DataType::String => DataValue::String(std::str::from_utf8(value_bytes)?.to_owned()),
+            DataType::Int8
+            | DataType::Int16
+            | DataType::Int24
+            | DataType::Int32
+            | DataType::Int48
+            | DataType::Int64 => DataValue::Int(decode_be_int(value_bytes)),
+            DataType::Float64 => DataValue::Float(f64::from_be_bytes(value_bytes.try_into()?)),
+            DataType::Zero => DataValue::Int(0),
+            DataType::One => DataValue::Int(1),
+            DataType::Blob => DataValue::Blob(value_bytes.to_owned()),
+            DataType::Null => DataValue::Null,
+        };
+        values.push(value);
+
+        values_offset += value_length;
+    }
+
+    Ok(values)
+}
+
+// Distinguishes which b-tree a payload's cell belongs to, since table and
+// index cells use different local-payload thresholds (see `read_payload`).
+#[derive(Clone, Copy)]
+enum CellKind {
+    Table,
+    Index,
+}
+
+#[derive(Debug)]
+struct DbRow {
+    rowid: i64,
+    values: Vec<DataValue>,
+}
+
 #[derive(Debug)]
 struct DbPage {
-    rows: Vec<Vec<DataValue>>,
+    rows: Vec<DbRow>,
 }
 
 #[derive(Debug)]
 struct DbTableConfig {
     table_name: String,
-    page_number: i8,
+    page_number: u32,
+}
+
+#[derive(Debug)]
+struct DbIndexConfig {
+    table_name: String,
+    column_name: String,
+    page_number: u32,
 }
 
 #[derive(Debug)]
@@ -105,99 +187,187 @@ impl Db {
         })
     }
 
-    fn get_page(&self, page_number: u16) -> Result<DbPage> {
-        let start_offset = if page_number == 1 { 100 } else { 0 };
+    // Reads the raw bytes of a page, trimming the 100-byte database header off
+    // page 1 so that offset 0 always lines up with the b-tree page header.
+    // Returns the bytes along with the offset that was trimmed, since on-page
+    // cell pointers are stored relative to the start of the page on disk.
+    fn read_page_bytes(&self, page_number: u32) -> Result<(Vec<u8>, u16)> {
+        let start_offset: u16 = if page_number == 1 { 100 } else { 0 };
 
         // align since pages 1 based
-        let page_number = page_number - 1;
+        let page_index = u64::from(page_number - 1);
 
         let mut file = File::open(&self.file_path)?;
         let mut bytes_to_read = vec![0; (self.page_size - start_offset) as usize];
         file.seek(SeekFrom::Start(
-            (page_number * self.page_size + start_offset) as u64,
+            page_index * u64::from(self.page_size) + u64::from(start_offset),
         ))?;
         file.read_exact(&mut bytes_to_read)?;
 
-        let num_of_cells = u16::from_be_bytes([bytes_to_read[3], bytes_to_read[4]]);
+        Ok((bytes_to_read, start_offset))
+    }
 
-        let mut rows: Vec<Vec<DataValue>> = vec![];
-        let cell_data_start_length = 8;
-        for i in 0..num_of_cells {
-            // cell offset on the bottom of the page
-            let cell_start_offset = u16::from_be_bytes([
-                bytes_to_read[cell_data_start_length + (i * 2) as usize],
-                bytes_to_read[cell_data_start_length + (i * 2 + 1) as usize],
-            ]);
+    // Assembles a cell's full payload, following the overflow-page chain
+    // when `payload_size` is larger than what fits locally on the page. Per
+    // the SQLite format, a payload of `payload_size` bytes starting at
+    // `page_bytes[start]` is stored in full unless it exceeds `max_local`; if
+    // it does, only `local_size` bytes are stored locally followed by a
+    // 4-byte overflow page number, and the rest is chained across overflow
+    // pages (each starting with its own 4-byte next-page pointer, 0 = end).
+    // `max_local` (X) differs between table leaf cells (U-35) and index
+    // cells (((U-12)*64/255)-23); the minimum local size (M) formula is the
+    // same for both, per the b-tree cell format.
+    fn read_payload(
+        &self,
+        page_bytes: &[u8],
+        start: usize,
+        payload_size: u64,
+        cell_kind: CellKind,
+    ) -> Result<Vec<u8>> {
+        let usable_size = self.page_size as u64;
+        let max_local = match cell_kind {
+            CellKind::Table => usable_size - 35,
+            CellKind::Index => (usable_size - 12) * 64 / 255 - 23,
+        };
+
+        if payload_size <= max_local {
+            return Ok(page_bytes[start..start + payload_size as usize].to_vec());
+        }
 
-            let mut row_offset: usize = (cell_start_offset - start_offset) as usize;
-            let (_cell_size, offset) = decode_varint(&bytes_to_read[row_offset..])?;
-            row_offset += offset;
+        let min_local = (usable_size - 12) * 32 / 255 - 23;
+        let surplus = min_local + (payload_size - min_local) % (usable_size - 4);
+        let local_size = (if surplus > max_local { min_local } else { surplus }) as usize;
 
-            let (_row_id, offset) = decode_varint(&bytes_to_read[row_offset..])?;
-            row_offset += offset;
+        let mut payload = page_bytes[start..start + local_size].to_vec();
+        let mut overflow_page = u32::from_be_bytes(
+            page_bytes[start + local_size..start + local_size + 4].try_into()?,
+        );
 
-            let (header_size, offset) = decode_varint(&bytes_to_read[row_offset..])?;
+        while overflow_page != 0 && (payload.len() as u64) < payload_size {
+            let (overflow_bytes, _start_offset) = self.read_page_bytes(overflow_page)?;
+            let next_page = u32::from_be_bytes(overflow_bytes[0..4].try_into()?);
 
-            let header_end_offset = row_offset + header_size as usize;
-            row_offset += offset;
+            let remaining = payload_size as usize - payload.len();
+            let available = overflow_bytes.len() - 4;
+            let take = remaining.min(available);
+            payload.extend_from_slice(&overflow_bytes[4..4 + take]);
 
-            let mut type_definitions: Vec<(DataType, usize)> = vec![];
-            while row_offset < header_end_offset {
-                let (content, offset) =
-                    decode_varint(&bytes_to_read[row_offset..header_end_offset])?;
-                row_offset += offset;
+            overflow_page = next_page;
+        }
 
-                let type_definition = get_type_definition(content);
-                type_definitions.push(type_definition);
-            }
+        Ok(payload)
+    }
 
-            let mut row_data: Vec<DataValue> = vec![];
-            let mut values_offset = header_end_offset;
-            for type_definition in type_definitions {
-                let value_length = type_definition.1;
-                let value_bytes = &bytes_to_read[values_offset..values_offset + value_length];
+    fn get_page(&self, page_number: u32) -> Result<DbPage> {
+        let mut rows: Vec<DbRow> = vec![];
+        self.collect_table_rows(page_number, &mut rows)?;
 
-                match type_definition.0 {
-                    DataType::String => {
-                        let value = std::str::from_utf8(value_bytes)?;
-                        row_data.push(DataValue::String(value.to_owned()));
-                    }
-                    DataType::Int8 => {
-                        let value_bytes =
-                            &bytes_to_read[values_offset..values_offset + value_length];
-                        let value = i8::from_be(value_bytes[0] as i8);
-                        row_data.push(DataValue::Int(value));
-                    }
-                    DataType::Null => row_data.push(DataValue::Null),
-                    _ => {
-                        row_data.push(DataValue::Unknown);
-                    }
+        Ok(DbPage { rows })
+    }
+
+    // Walks a table b-tree rooted at `page_number`, appending leaf rows (in
+    // key order) to `rows`. Interior table pages (type 5) only hold routing
+    // cells, so they are recursed into rather than producing rows themselves.
+    fn collect_table_rows(&self, page_number: u32, rows: &mut Vec<DbRow>) -> Result<()> {
+        let (bytes_to_read, start_offset) = self.read_page_bytes(page_number)?;
+
+        let page_type = bytes_to_read[0];
+        let num_of_cells = u16::from_be_bytes([bytes_to_read[3], bytes_to_read[4]]);
+
+        match page_type {
+            // interior table page
+            5 => {
+                let cell_data_start_length = 12;
+                for i in 0..num_of_cells {
+                    let cell_pointer_offset = cell_data_start_length + (i * 2) as usize;
+                    let cell_start_offset = u16::from_be_bytes([
+                        bytes_to_read[cell_pointer_offset],
+                        bytes_to_read[cell_pointer_offset + 1],
+                    ]);
+                    let cell_offset: usize = (cell_start_offset - start_offset) as usize;
+
+                    let left_child_page = u32::from_be_bytes([
+                        bytes_to_read[cell_offset],
+                        bytes_to_read[cell_offset + 1],
+                        bytes_to_read[cell_offset + 2],
+                        bytes_to_read[cell_offset + 3],
+                    ]);
+                    self.collect_table_rows(left_child_page, rows)?;
                 }
 
-                values_offset += value_length;
+                let right_most_pointer = u32::from_be_bytes([
+                    bytes_to_read[8],
+                    bytes_to_read[9],
+                    bytes_to_read[10],
+                    bytes_to_read[11],
+                ]);
+                self.collect_table_rows(right_most_pointer, rows)?;
             }
-
-            rows.push(row_data);
+            // leaf table page
+            13 => {
+                let cell_data_start_length = 8;
+                for i in 0..num_of_cells {
+                    // cell offset on the bottom of the page
+                    let cell_start_offset = u16::from_be_bytes([
+                        bytes_to_read[cell_data_start_length + (i * 2) as usize],
+                        bytes_to_read[cell_data_start_length + (i * 2 + 1) as usize],
+                    ]);
+
+                    let mut row_offset: usize = (cell_start_offset - start_offset) as usize;
+                    let (payload_size, offset) = decode_varint(&bytes_to_read[row_offset..])?;
+                    row_offset += offset;
+
+                    let (row_id, offset) = decode_varint(&bytes_to_read[row_offset..])?;
+                    row_offset += offset;
+
+                    let payload =
+                        self.read_payload(&bytes_to_read, row_offset, payload_size, CellKind::Table)?;
+                    let row_data = parse_record(&payload)?;
+
+                    rows.push(DbRow {
+                        rowid: row_id as i64,
+                        values: row_data,
+                    });
+                }
+            }
+            other => bail!("Unexpected table b-tree page type: {}", other),
         }
 
-        Ok(DbPage { rows })
+        Ok(())
     }
 
     fn get_table_configs(&self) -> Result<Vec<DbTableConfig>> {
         let page = self.get_page(1)?;
-        let name_column_index = 2;
+        let type_column_index = 0;
+        let name_column_index = 1;
         let page_column_index = 3;
 
         Ok(page
             .rows
             .iter()
             .filter_map(|row| {
-                let table_name = match row.get(name_column_index).unwrap_or(&DataValue::Unknown) {
+                let is_table = matches!(
+                    row.values.get(type_column_index),
+                    Some(DataValue::String(val)) if val == "table"
+                );
+                if !is_table {
+                    return None;
+                }
+
+                let table_name = match row
+                    .values
+                    .get(name_column_index)
+                    .unwrap_or(&DataValue::Null)
+                {
                     DataValue::String(val) => val.clone(),
                     _ => "".into(),
                 };
-                let page_number = match row.get(page_column_index).unwrap_or(&DataValue::Unknown) {
-                    DataValue::Int(val) => val.clone(),
+                let page_number = match row
+                    .values
+                    .get(page_column_index)
+                    .unwrap_or(&DataValue::Null)
+                {
+                    DataValue::Int(val) => *val as u32,
                     _ => 0,
                 };
 
@@ -222,18 +392,583 @@ impl Db {
             .collect::<Vec<String>>());
     }
 
+    fn get_table_config(&self, table_name: &str) -> Result<DbTableConfig> {
+        self.get_table_configs()?
+            .into_iter()
+            .find(|config| config.table_name == table_name)
+            .ok_or(anyhow!("No data for table"))
+    }
+
     fn get_table_page(&self, table_name: &str) -> Result<DbPage> {
-        let configs = self.get_table_configs()?;
-        let config = configs
+        let config = self.get_table_config(table_name)?;
+
+        self.get_page(config.page_number)
+    }
+
+    // Descends a table b-tree by rowid, following the interior-page routing
+    // cells (which carry the largest rowid contained in their left child) until
+    // the leaf cell with a matching rowid is found.
+    fn find_table_row(&self, page_number: u32, rowid: i64) -> Result<Option<DbRow>> {
+        let (bytes_to_read, start_offset) = self.read_page_bytes(page_number)?;
+
+        let page_type = bytes_to_read[0];
+        let num_of_cells = u16::from_be_bytes([bytes_to_read[3], bytes_to_read[4]]);
+
+        match page_type {
+            // interior table page
+            5 => {
+                let cell_data_start_length = 12;
+                for i in 0..num_of_cells {
+                    let cell_pointer_offset = cell_data_start_length + (i * 2) as usize;
+                    let cell_start_offset = u16::from_be_bytes([
+                        bytes_to_read[cell_pointer_offset],
+                        bytes_to_read[cell_pointer_offset + 1],
+                    ]);
+                    let cell_offset: usize = (cell_start_offset - start_offset) as usize;
+
+                    let left_child_page = u32::from_be_bytes([
+                        bytes_to_read[cell_offset],
+                        bytes_to_read[cell_offset + 1],
+                        bytes_to_read[cell_offset + 2],
+                        bytes_to_read[cell_offset + 3],
+                    ]);
+                    let (cell_rowid, _offset) = decode_varint(&bytes_to_read[cell_offset + 4..])?;
+
+                    if rowid <= cell_rowid as i64 {
+                        return self.find_table_row(left_child_page, rowid);
+                    }
+                }
+
+                let right_most_pointer = u32::from_be_bytes([
+                    bytes_to_read[8],
+                    bytes_to_read[9],
+                    bytes_to_read[10],
+                    bytes_to_read[11],
+                ]);
+
+                self.find_table_row(right_most_pointer, rowid)
+            }
+            // leaf table page
+            13 => {
+                let cell_data_start_length = 8;
+                for i in 0..num_of_cells {
+                    let cell_start_offset = u16::from_be_bytes([
+                        bytes_to_read[cell_data_start_length + (i * 2) as usize],
+                        bytes_to_read[cell_data_start_length + (i * 2 + 1) as usize],
+                    ]);
+
+                    let mut row_offset: usize = (cell_start_offset - start_offset) as usize;
+                    let (payload_size, offset) = decode_varint(&bytes_to_read[row_offset..])?;
+                    row_offset += offset;
+
+                    let (row_id, offset) = decode_varint(&bytes_to_read[row_offset..])?;
+                    row_offset += offset;
+
+                    if row_id as i64 == rowid {
+                        let payload = self.read_payload(
+                            &bytes_to_read,
+                            row_offset,
+                            payload_size,
+                            CellKind::Table,
+                        )?;
+                        let values = parse_record(&payload)?;
+
+                        return Ok(Some(DbRow {
+                            rowid: row_id as i64,
+                            values,
+                        }));
+                    }
+                }
+
+                Ok(None)
+            }
+            other => bail!("Unexpected table b-tree page type: {}", other),
+        }
+    }
+
+    fn get_table_row(&self, table_name: &str, rowid: i64) -> Result<Option<DbRow>> {
+        let config = self.get_table_config(table_name)?;
+
+        self.find_table_row(config.page_number, rowid)
+    }
+
+    fn get_index_configs(&self) -> Result<Vec<DbIndexConfig>> {
+        let schema = self.get_page(1)?;
+        let type_column_index = 0;
+        let tbl_name_column_index = 2;
+        let page_column_index = 3;
+        let sql_column_index = 4;
+
+        Ok(schema
+            .rows
             .iter()
-            .find(|config| config.table_name == table_name)
-            .ok_or(anyhow!("No data for table"))?;
+            .filter_map(|row| {
+                let is_index = matches!(
+                    row.values.get(type_column_index),
+                    Some(DataValue::String(val)) if val == "index"
+                );
+                if !is_index {
+                    return None;
+                }
+
+                let table_name = match row.values.get(tbl_name_column_index) {
+                    Some(DataValue::String(val)) => val.clone(),
+                    _ => return None,
+                };
+                let page_number = match row.values.get(page_column_index) {
+                    Some(DataValue::Int(val)) => *val as u32,
+                    _ => return None,
+                };
+                // Implicit sqlite_autoindex_* entries (e.g. from UNIQUE
+                // constraints) store a NULL sql column and can't be used here.
+                let sql = match row.values.get(sql_column_index) {
+                    Some(DataValue::String(val)) => val,
+                    _ => return None,
+                };
+                let column_name = parse_create_index_column(sql).ok()?;
+
+                Some(DbIndexConfig {
+                    table_name,
+                    column_name,
+                    page_number,
+                })
+            })
+            .collect())
+    }
 
-        self.get_page(config.page_number as u16)
+    fn get_index_config(
+        &self,
+        table_name: &str,
+        column_name: &str,
+    ) -> Result<Option<DbIndexConfig>> {
+        Ok(self.get_index_configs()?.into_iter().find(|config| {
+            config.table_name == table_name && config.column_name.eq_ignore_ascii_case(column_name)
+        }))
     }
+
+    // Descends an index b-tree looking for entries whose indexed value
+    // equals `key`, appending their rowids to `rowids`. Index pages store
+    // full `(value, rowid)` entries at every level (not just the leaves),
+    // so interior pages are scanned the same way as leaves, plus recursion
+    // into child pages.
+    fn collect_index_rowids(&self, page_number: u32, key: &str, rowids: &mut Vec<i64>) -> Result<()> {
+        let (bytes_to_read, start_offset) = self.read_page_bytes(page_number)?;
+
+        let page_type = bytes_to_read[0];
+        let num_of_cells = u16::from_be_bytes([bytes_to_read[3], bytes_to_read[4]]);
+
+        match page_type {
+            // interior index page
+            2 => {
+                let cell_data_start_length = 12;
+                for i in 0..num_of_cells {
+                    let cell_pointer_offset = cell_data_start_length + (i * 2) as usize;
+                    let cell_start_offset = u16::from_be_bytes([
+                        bytes_to_read[cell_pointer_offset],
+                        bytes_to_read[cell_pointer_offset + 1],
+                    ]);
+                    let cell_offset: usize = (cell_start_offset - start_offset) as usize;
+
+                    let left_child_page = u32::from_be_bytes([
+                        bytes_to_read[cell_offset],
+                        bytes_to_read[cell_offset + 1],
+                        bytes_to_read[cell_offset + 2],
+                        bytes_to_read[cell_offset + 3],
+                    ]);
+                    let (payload_size, offset) = decode_varint(&bytes_to_read[cell_offset + 4..])?;
+                    let record_offset = cell_offset + 4 + offset;
+                    let payload = self.read_payload(
+                        &bytes_to_read,
+                        record_offset,
+                        payload_size,
+                        CellKind::Index,
+                    )?;
+                    let values = parse_record(&payload)?;
+                    let (value, rowid) = parse_index_entry(values)?;
+
+                    match compare_indexed_value(&value, key) {
+                        std::cmp::Ordering::Less => {}
+                        std::cmp::Ordering::Equal => {
+                            self.collect_index_rowids(left_child_page, key, rowids)?;
+                            rowids.push(rowid);
+                        }
+                        std::cmp::Ordering::Greater => {
+                            self.collect_index_rowids(left_child_page, key, rowids)?;
+                            return Ok(());
+                        }
+                    }
+                }
+
+                let right_most_pointer = u32::from_be_bytes([
+                    bytes_to_read[8],
+                    bytes_to_read[9],
+                    bytes_to_read[10],
+                    bytes_to_read[11],
+                ]);
+
+                self.collect_index_rowids(right_most_pointer, key, rowids)
+            }
+            // leaf index page
+            10 => {
+                let cell_data_start_length = 8;
+                for i in 0..num_of_cells {
+                    let cell_start_offset = u16::from_be_bytes([
+                        bytes_to_read[cell_data_start_length + (i * 2) as usize],
+                        bytes_to_read[cell_data_start_length + (i * 2 + 1) as usize],
+                    ]);
+                    let cell_offset: usize = (cell_start_offset - start_offset) as usize;
+
+                    let (payload_size, offset) = decode_varint(&bytes_to_read[cell_offset..])?;
+                    let record_offset = cell_offset + offset;
+                    let payload = self.read_payload(
+                        &bytes_to_read,
+                        record_offset,
+                        payload_size,
+                        CellKind::Index,
+                    )?;
+                    let values = parse_record(&payload)?;
+                    let (value, rowid) = parse_index_entry(values)?;
+
+                    if compare_indexed_value(&value, key) == std::cmp::Ordering::Equal {
+                        rowids.push(rowid);
+                    }
+                }
+
+                Ok(())
+            }
+            other => bail!("Unexpected index b-tree page type: {}", other),
+        }
+    }
+
+    // Looks up rowids via `index_config` and fetches the matching rows from
+    // `table_name`'s table b-tree, turning an O(table) scan into O(log n).
+    fn lookup_by_index(
+        &self,
+        index_config: &DbIndexConfig,
+        table_name: &str,
+        key: &str,
+    ) -> Result<Vec<DbRow>> {
+        let mut rowids = vec![];
+        self.collect_index_rowids(index_config.page_number, key, &mut rowids)?;
+
+        rowids
+            .into_iter()
+            .filter_map(|rowid| self.get_table_row(table_name, rowid).transpose())
+            .collect()
+    }
+
+    // Finds the `CREATE TABLE`/`CREATE INDEX` statement stored in `sqlite_schema`
+    // for an object of the given `type` (e.g. "table" or "index") and name.
+    fn get_schema_sql(&self, object_type: &str, name: &str) -> Result<String> {
+        let schema = self.get_page(1)?;
+        let type_column_index = 0;
+        let name_column_index = 1;
+        let sql_column_index = 4;
+
+        schema
+            .rows
+            .iter()
+            .find(|row| {
+                let matches_type = matches!(
+                    row.values.get(type_column_index),
+                    Some(DataValue::String(val)) if val == object_type
+                );
+                let matches_name = matches!(
+                    row.values.get(name_column_index),
+                    Some(DataValue::String(val)) if val == name
+                );
+
+                matches_type && matches_name
+            })
+            .and_then(|row| match row.values.get(sql_column_index) {
+                Some(DataValue::String(sql)) => Some(sql.clone()),
+                _ => None,
+            })
+            .ok_or(anyhow!("No schema found for {} {}", object_type, name))
+    }
+
+    // Resolves a table's column names, in declaration order, along with the
+    // name of its `INTEGER PRIMARY KEY` column (a rowid alias), if any.
+    fn get_table_columns(&self, table_name: &str) -> Result<(Vec<String>, Option<String>)> {
+        let sql = self.get_schema_sql("table", table_name)?;
+
+        parse_create_table_columns(&sql)
+    }
+}
+
+// Parses the column list out of a `CREATE TABLE name (...)` statement,
+// returning the column names in order along with the name of the
+// `INTEGER PRIMARY KEY` column, if the table declares one as a rowid alias.
+fn parse_create_table_columns(sql: &str) -> Result<(Vec<String>, Option<String>)> {
+    let start = sql
+        .find('(')
+        .ok_or_else(|| anyhow!("Malformed CREATE TABLE statement: {}", sql))?;
+    let end = sql
+        .rfind(')')
+        .ok_or_else(|| anyhow!("Malformed CREATE TABLE statement: {}", sql))?;
+    let body = &sql[start + 1..end];
+
+    let mut columns = vec![];
+    let mut integer_primary_key = None;
+    let mut depth = 0;
+    let mut current = String::new();
+    for ch in body.chars() {
+        match ch {
+            '(' => {
+                depth += 1;
+                current.push(ch);
+            }
+            ')' => {
+                depth -= 1;
+                current.push(ch);
+            }
+            ',' if depth == 0 => {
+                push_column_def(&current, &mut columns, &mut integer_primary_key);
+                current.clear();
+            }
+            _ => current.push(ch),
+        }
+    }
+    push_column_def(&current, &mut columns, &mut integer_primary_key);
+
+    Ok((columns, integer_primary_key))
+}
+
+fn push_column_def(
+    def: &str,
+    columns: &mut Vec<String>,
+    integer_primary_key: &mut Option<String>,
+) {
+    let def = def.trim();
+    if def.is_empty() {
+        return;
+    }
+
+    let upper = def.to_uppercase();
+    // Table-level constraints don't introduce a column.
+    if upper.starts_with("PRIMARY KEY")
+        || upper.starts_with("UNIQUE")
+        || upper.starts_with("FOREIGN KEY")
+        || upper.starts_with("CHECK")
+        || upper.starts_with("CONSTRAINT")
+    {
+        return;
+    }
+
+    let name = def
+        .split_whitespace()
+        .next()
+        .unwrap_or("")
+        .trim_matches(|c| c == '"' || c == '`' || c == '[' || c == ']')
+        .to_owned();
+
+    if upper.contains("INTEGER PRIMARY KEY") {
+        *integer_primary_key = Some(name.clone());
+    }
+
+    columns.push(name);
+}
+
+// Parses the indexed column out of a `CREATE INDEX name ON table (column)`
+// statement. Only the first column is returned, which is all that's needed
+// to accelerate a single-column equality filter.
+fn parse_create_index_column(sql: &str) -> Result<String> {
+    let start = sql
+        .find('(')
+        .ok_or_else(|| anyhow!("Malformed CREATE INDEX statement: {}", sql))?;
+    let end = sql
+        .rfind(')')
+        .ok_or_else(|| anyhow!("Malformed CREATE INDEX statement: {}", sql))?;
+
+    let column = sql[start + 1..end]
+        .split(',')
+        .next()
+        .unwrap_or("")
+        .split_whitespace()
+        .next()
+        .unwrap_or("")
+        .trim_matches(|c| c == '"' || c == '`' || c == '[' || c == ']')
+        .to_owned();
+
+    Ok(column)
+}
+
+// Extracts the `(indexed-value, rowid)` pair from a parsed index entry record.
+fn parse_index_entry(mut values: Vec<DataValue>) -> Result<(DataValue, i64)> {
+    let rowid = match values.pop() {
+        Some(DataValue::Int(val)) => val,
+        _ => bail!("Index entry has no rowid"),
+    };
+    let value = values
+        .into_iter()
+        .next()
+        .ok_or_else(|| anyhow!("Index entry has no indexed value"))?;
+
+    Ok((value, rowid))
+}
+
+// Compares an index entry's typed value against a `WHERE` literal the way
+// SQLite's index b-tree orders entries: numerically for INTEGER/REAL
+// columns, by bytes for TEXT/BLOB. `key` is always parsed out of the query
+// as a string, so numeric columns parse it back into a number before
+// comparing; if that parse fails the value is incomparable to the key and
+// sorts before it, matching SQLite's type-affinity ordering rules.
+fn compare_indexed_value(value: &DataValue, key: &str) -> std::cmp::Ordering {
+    match value {
+        DataValue::Null => std::cmp::Ordering::Less,
+        DataValue::Int(val) => match key.parse::<i64>() {
+            Ok(key_val) => val.cmp(&key_val),
+            Err(_) => std::cmp::Ordering::Less,
+        },
+        DataValue::Float(val) => match key.parse::<f64>() {
+            Ok(key_val) => val.partial_cmp(&key_val).unwrap_or(std::cmp::Ordering::Less),
+            Err(_) => std::cmp::Ordering::Less,
+        },
+        DataValue::String(val) => val.as_str().cmp(key),
+        DataValue::Blob(val) => val.as_slice().cmp(key.as_bytes()),
+    }
+}
+
+struct SelectQuery {
+    columns: Vec<String>,
+    table: String,
+    filter: Option<(String, String)>,
+}
+
+// Parses `SELECT <cols> FROM <table> [WHERE <col> = <value>]`, case-insensitively.
+fn parse_select(query: &str) -> Result<SelectQuery> {
+    let upper = query.to_uppercase();
+    if !upper.starts_with("SELECT ") {
+        bail!("Expected a SELECT statement: {}", query);
+    }
+
+    let from_offset = upper
+        .find(" FROM ")
+        .ok_or_else(|| anyhow!("Missing FROM clause in query: {}", query))?;
+    let columns = query["SELECT ".len()..from_offset]
+        .split(',')
+        .map(|col| col.trim().to_owned())
+        .collect();
+
+    let rest = &query[from_offset + " FROM ".len()..];
+    let rest_upper = &upper[from_offset + " FROM ".len()..];
+
+    let (table, filter) = match rest_upper.find(" WHERE ") {
+        Some(where_offset) => {
+            let table = rest[..where_offset].trim().to_owned();
+            let condition = rest[where_offset + " WHERE ".len()..].trim();
+            let (column, value) = condition
+                .split_once('=')
+                .ok_or_else(|| anyhow!("Unsupported WHERE clause: {}", condition))?;
+            let value = value.trim().trim_matches('\'').trim_matches('"').to_owned();
+
+            (table, Some((column.trim().to_owned(), value)))
+        }
+        None => (rest.trim().to_owned(), None),
+    };
+
+    Ok(SelectQuery {
+        columns,
+        table,
+        filter,
+    })
+}
+
+fn format_value(value: &DataValue) -> String {
+    match value {
+        DataValue::Null => "".to_owned(),
+        DataValue::String(val) => val.clone(),
+        DataValue::Int(val) => val.to_string(),
+        DataValue::Float(val) => val.to_string(),
+        DataValue::Blob(val) => String::from_utf8_lossy(val).into_owned(),
+    }
+}
+
+// Reads column `index` of `row`, substituting the rowid when that column is
+// an `INTEGER PRIMARY KEY` alias (its stored value is always NULL).
+fn column_value(row: &DbRow, index: usize, is_rowid_alias: bool) -> String {
+    if is_rowid_alias {
+        return row.rowid.to_string();
+    }
+
+    row.values
+        .get(index)
+        .map(format_value)
+        .unwrap_or_default()
+}
+
+fn run_select(db: &Db, query: &str) -> Result<()> {
+    if query.to_uppercase().contains("COUNT(*)") {
+        let table_name = query
+            .split_whitespace()
+            .last()
+            .ok_or_else(|| anyhow!("Requested table does not exist: {}", query))?;
+        let table_page = db.get_table_page(table_name)?;
+        println!("{}", table_page.rows.len());
+
+        return Ok(());
+    }
+
+    let select = parse_select(query)?;
+    let (columns, integer_primary_key) = db.get_table_columns(&select.table)?;
+
+    let column_index = |name: &str| {
+        columns
+            .iter()
+            .position(|col| col.eq_ignore_ascii_case(name))
+            .ok_or_else(|| anyhow!("No column {} in table {}", name, select.table))
+    };
+    let is_rowid_alias =
+        |index: usize| integer_primary_key.as_deref() == Some(columns[index].as_str());
+
+    let selected = select
+        .columns
+        .iter()
+        .map(|col| column_index(col))
+        .collect::<Result<Vec<usize>>>()?;
+
+    // If the WHERE column has an index, look the rowids up there instead of
+    // scanning the whole table; the rows it returns are already filtered.
+    let (rows, already_filtered) = match &select.filter {
+        Some((column, value)) if !is_rowid_alias(column_index(column)?) => {
+            match db.get_index_config(&select.table, column)? {
+                Some(index_config) => (
+                    db.lookup_by_index(&index_config, &select.table, value)?,
+                    true,
+                ),
+                None => (db.get_table_page(&select.table)?.rows, false),
+            }
+        }
+        _ => (db.get_table_page(&select.table)?.rows, false),
+    };
+
+    let filter = if already_filtered {
+        None
+    } else {
+        select
+            .filter
+            .as_ref()
+            .map(|(col, value)| column_index(col).map(|index| (index, value.as_str())))
+            .transpose()?
+    };
+
+    for row in &rows {
+        if let Some((index, value)) = filter {
+            if column_value(row, index, is_rowid_alias(index)) != value {
+                continue;
+            }
+        }
+
+        let projected = selected
+            .iter()
+            .map(|&index| column_value(row, index, is_rowid_alias(index)))
+            .collect::<Vec<String>>();
+        println!("{}", projected.join("|"));
+    }
+
+    Ok(())
 }
 
-// TODO: process all ?
 fn main() -> Result<()> {
     // Parse arguments
     let args = std::env::args().collect::<Vec<_>>();
@@ -256,15 +991,8 @@ fn main() -> Result<()> {
             println!("{:?}", db.get_table_names()?.join(" "));
         }
         other => {
-            // TODO: case sensitivity
-            if other.starts_with("SELECT") {
-                let table_name = other.split_whitespace().last();
-                if let Some(table_name) = table_name {
-                    let table_page = db.get_table_page(table_name)?;
-                    println!("{}", table_page.rows.len());
-                } else {
-                    bail!("Requested table does not exist {}", command);
-                }
+            if other.to_uppercase().starts_with("SELECT") {
+                run_select(&db, other)?;
             } else {
                 bail!("Missing or invalid command passed: {}", command);
             }